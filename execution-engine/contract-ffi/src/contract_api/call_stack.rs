@@ -0,0 +1,117 @@
+use crate::{
+    bytesrepr::{self, FromBytes},
+    value::account::PublicKey,
+};
+
+/// Fixed width of a package or contract hash.
+pub const HASH_LENGTH: usize = 32;
+
+/// The kind of code executing in a stored-session or stored-contract frame.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EntryPointType {
+    /// Runs in the caller's context (stored session code).
+    Session,
+    /// Runs in its own context (stored contract code).
+    Contract,
+}
+
+impl FromBytes for EntryPointType {
+    fn from_bytes(bytes: &[u8]) -> Result<(EntryPointType, &[u8]), bytesrepr::Error> {
+        let (tag, remainder) = u8::from_bytes(bytes)?;
+        match tag {
+            0 => Ok((EntryPointType::Session, remainder)),
+            1 => Ok((EntryPointType::Contract, remainder)),
+            _ => Err(bytesrepr::Error::FormattingError),
+        }
+    }
+}
+
+/// A single frame of the current call stack, from the originating session account down to the
+/// frame currently executing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CallStackElement {
+    /// The session account that originated the deploy.
+    Session { account: PublicKey },
+    /// Stored session code running in the caller's context.
+    StoredSession {
+        package_hash: [u8; HASH_LENGTH],
+        contract_hash: [u8; HASH_LENGTH],
+        entry_point_type: EntryPointType,
+    },
+    /// Stored contract code running in its own context.
+    StoredContract {
+        package_hash: [u8; HASH_LENGTH],
+        contract_hash: [u8; HASH_LENGTH],
+        entry_point_type: EntryPointType,
+    },
+}
+
+fn hash_from_bytes(bytes: &[u8]) -> Result<([u8; HASH_LENGTH], &[u8]), bytesrepr::Error> {
+    // Fixed-width hashes serialize as `HASH_LENGTH` raw bytes, matching the array `ToBytes` impl
+    // (no length prefix), so we slice the bytes directly rather than reading a `Vec<u8>`.
+    if bytes.len() < HASH_LENGTH {
+        return Err(bytesrepr::Error::EarlyEndOfStream);
+    }
+    let (hash_bytes, remainder) = bytes.split_at(HASH_LENGTH);
+    let mut out = [0u8; HASH_LENGTH];
+    out.copy_from_slice(hash_bytes);
+    Ok((out, remainder))
+}
+
+impl FromBytes for CallStackElement {
+    fn from_bytes(bytes: &[u8]) -> Result<(CallStackElement, &[u8]), bytesrepr::Error> {
+        let (tag, remainder) = u8::from_bytes(bytes)?;
+        match tag {
+            0 => {
+                let (account, remainder) = PublicKey::from_bytes(remainder)?;
+                Ok((CallStackElement::Session { account }, remainder))
+            }
+            1 => {
+                let (package_hash, remainder) = hash_from_bytes(remainder)?;
+                let (contract_hash, remainder) = hash_from_bytes(remainder)?;
+                let (entry_point_type, remainder) = EntryPointType::from_bytes(remainder)?;
+                Ok((
+                    CallStackElement::StoredSession {
+                        package_hash,
+                        contract_hash,
+                        entry_point_type,
+                    },
+                    remainder,
+                ))
+            }
+            2 => {
+                let (package_hash, remainder) = hash_from_bytes(remainder)?;
+                let (contract_hash, remainder) = hash_from_bytes(remainder)?;
+                let (entry_point_type, remainder) = EntryPointType::from_bytes(remainder)?;
+                Ok((
+                    CallStackElement::StoredContract {
+                        package_hash,
+                        contract_hash,
+                        entry_point_type,
+                    },
+                    remainder,
+                ))
+            }
+            _ => Err(bytesrepr::Error::FormattingError),
+        }
+    }
+}
+
+impl CallStackElement {
+    /// Returns the contract hash of this frame, if it is a stored frame.
+    pub fn contract_hash(&self) -> Option<&[u8; HASH_LENGTH]> {
+        match self {
+            CallStackElement::Session { .. } => None,
+            CallStackElement::StoredSession { contract_hash, .. }
+            | CallStackElement::StoredContract { contract_hash, .. } => Some(contract_hash),
+        }
+    }
+
+    /// Returns the originating account's public key, if this frame is a session account.
+    pub fn account(&self) -> Option<&PublicKey> {
+        match self {
+            CallStackElement::Session { account } => Some(account),
+            _ => None,
+        }
+    }
+}
@@ -1,31 +1,96 @@
-use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use core::mem::MaybeUninit;
 
 use crate::{
     args_parser::ArgsParser,
-    block_time::{BlockTime, BLOCKTIME_SER_SIZE},
+    block_time::BlockTime,
     bytesrepr::{deserialize, FromBytes},
     contract_api::{
         self,
+        call_stack::CallStackElement,
         error::{self, Error},
+        runtime_args::RuntimeArgs,
         ContractRef,
     },
-    execution::{Phase, PHASE_SIZE},
+    execution::Phase,
     ext_ffi,
     key::Key,
     unwrap_or_revert::UnwrapOrRevert,
     uref::URef,
-    value::{account::PublicKey, CLTyped, CLValue, CLValueError},
+    value::{account::PublicKey, CLTyped, CLValue, CLValueError, Gas, U512},
 };
 
+/// Bitset controlling how [`call_contract_with_flags`] passes input and returns output.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CallFlags(u32);
+
+impl CallFlags {
+    /// Default behaviour: copy the supplied arguments in and the result out.
+    pub const NONE: CallFlags = CallFlags(0);
+    /// Pass the caller's own input through to the callee untouched.
+    pub const FORWARD_INPUT: CallFlags = CallFlags(0b0001);
+    /// Copy the caller's input into the callee.
+    pub const CLONE_INPUT: CallFlags = CallFlags(0b0010);
+    /// Return the callee's output directly as the caller's return value.
+    pub const TAIL_CALL: CallFlags = CallFlags(0b0100);
+    /// Explicitly permit re-entering the currently running contract.
+    pub const ALLOW_REENTRY: CallFlags = CallFlags(0b1000);
+
+    /// Returns the raw bits for passing across the FFI boundary.
+    pub fn bits(self) -> u32 {
+        self.0
+    }
+
+    /// Returns `true` if all of `other`'s bits are set in `self`.
+    pub fn contains(self, other: CallFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl core::ops::BitOr for CallFlags {
+    type Output = CallFlags;
+
+    fn bitor(self, rhs: CallFlags) -> CallFlags {
+        CallFlags(self.0 | rhs.0)
+    }
+}
+
+/// Bitset controlling how [`ret_with_flags`] terminates the current module.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ReturnFlags(u32);
+
+impl ReturnFlags {
+    /// Normal return: hand back a value and keep the state changes.
+    pub const NONE: ReturnFlags = ReturnFlags(0);
+    /// Revert return: hand back a value but discard the state changes.
+    pub const REVERT: ReturnFlags = ReturnFlags(0b0001);
+
+    /// Returns the raw bits for passing across the FFI boundary.
+    pub fn bits(self) -> u32 {
+        self.0
+    }
+}
+
 /// Returns `value` to the host, terminating the currently running module.
 ///
 /// Note this function is only relevant to contracts stored on chain which return a value to their
 /// caller. The return value of a directly deployed contract is never looked at.
 pub fn ret(value: CLValue, extra_urefs: Vec<URef>) -> ! {
+    ret_with_flags(value, extra_urefs, ReturnFlags::NONE)
+}
+
+/// Like [`ret`], but lets the contract signal a "revert" return (state changes discarded) while
+/// still handing back a `CLValue`.
+pub fn ret_with_flags(value: CLValue, extra_urefs: Vec<URef>, flags: ReturnFlags) -> ! {
     let (ptr, size, _bytes) = contract_api::to_ptr(value);
     let (urefs_ptr, urefs_size, _bytes2) = contract_api::to_ptr(extra_urefs);
     unsafe {
-        ext_ffi::ret(ptr, size, urefs_ptr, urefs_size);
+        ext_ffi::ret(ptr, size, urefs_ptr, urefs_size, flags.bits());
     }
 }
 
@@ -36,12 +101,59 @@ pub fn revert<T: Into<Error>>(error: T) -> ! {
     }
 }
 
-/// Call the given contract, passing the given (serialized) arguments to
-/// the host in order to have them available to the called contract during its
-/// execution. The value returned from the contract call (see `ret` above) is
-/// returned from this function.
+/// Call the given contract, passing the given named arguments to the host in order to have them
+/// available to the called contract during its execution. Arguments are looked up by name against
+/// the `Parameter` list registered in the callee's `EntryPoint`, so reordering the declaration no
+/// longer breaks callers. The value returned from the contract call (see `ret` above) is returned
+/// from this function.
+pub fn call_contract(c_ptr: ContractRef, args: RuntimeArgs, extra_urefs: Vec<Key>) -> CLValue {
+    call_contract_with_flags(c_ptr, args, extra_urefs, CallFlags::NONE)
+}
+
+/// Like [`call_contract`], but threads a [`CallFlags`] bitset to the host so that proxy/forwarding
+/// contracts and controlled reentrancy are possible. When `flags` contains [`CallFlags::TAIL_CALL`]
+/// the callee's output becomes the caller's return value directly, so the result is not copied back
+/// or re-deserialized here.
+pub fn call_contract_with_flags(
+    c_ptr: ContractRef,
+    args: RuntimeArgs,
+    extra_urefs: Vec<Key>,
+    flags: CallFlags,
+) -> CLValue {
+    let contract_key: Key = c_ptr.into();
+    let (key_ptr, key_size, _bytes1) = contract_api::to_ptr(contract_key);
+    let (args_ptr, args_size, _bytes2) = contract_api::to_ptr(args);
+    let (urefs_ptr, urefs_size, _bytes3) = contract_api::to_ptr(extra_urefs);
+    let res_size = unsafe {
+        ext_ffi::call_contract(
+            key_ptr,
+            key_size,
+            args_ptr,
+            args_size,
+            urefs_ptr,
+            urefs_size,
+            flags.bits(),
+        )
+    };
+    if flags.contains(CallFlags::TAIL_CALL) {
+        return CLValue::from_t(()).unwrap_or_revert();
+    }
+    let res_ptr = contract_api::alloc_bytes(res_size);
+    let res_bytes = unsafe {
+        ext_ffi::get_call_result(res_ptr);
+        Vec::from_raw_parts(res_ptr, res_size, res_size)
+    };
+    deserialize(&res_bytes).unwrap_or_revert()
+}
+
+/// Thin positional shim retained for contracts that have not yet migrated to the named
+/// [`RuntimeArgs`] surface. New code should prefer [`call_contract`].
 #[allow(clippy::ptr_arg)]
-pub fn call_contract<A: ArgsParser>(c_ptr: ContractRef, args: A, extra_urefs: Vec<Key>) -> CLValue {
+pub fn call_contract_with_args_parser<A: ArgsParser>(
+    c_ptr: ContractRef,
+    args: A,
+    extra_urefs: Vec<Key>,
+) -> CLValue {
     let contract_key: Key = c_ptr.into();
     let (key_ptr, key_size, _bytes1) = contract_api::to_ptr(contract_key);
     let (args_ptr, args_size, _bytes2) = ArgsParser::parse(args)
@@ -50,7 +162,13 @@ pub fn call_contract<A: ArgsParser>(c_ptr: ContractRef, args: A, extra_urefs: Ve
     let (urefs_ptr, urefs_size, _bytes3) = contract_api::to_ptr(extra_urefs);
     let res_size = unsafe {
         ext_ffi::call_contract(
-            key_ptr, key_size, args_ptr, args_size, urefs_ptr, urefs_size,
+            key_ptr,
+            key_size,
+            args_ptr,
+            args_size,
+            urefs_ptr,
+            urefs_size,
+            CallFlags::NONE.bits(),
         )
     };
     let res_ptr = contract_api::alloc_bytes(res_size);
@@ -76,27 +194,43 @@ pub fn upgrade_contract_at_uref(name: &str, uref: URef) {
     }
 }
 
-fn load_arg(index: u32) -> Option<usize> {
-    let arg_size = unsafe { ext_ffi::load_arg(index) };
-    if arg_size >= 0 {
-        Some(arg_size as usize)
+/// Copies `size` bytes out of the host's reusable result buffer into a fresh `Vec`.
+///
+/// The getters in this module that use it (`get_arg`, `get_caller`, `get_blocktime`, `get_phase`,
+/// `get_gas_left`, `get_minimum_balance`, `get_key`, `event_count`, `list_named_keys`) follow the
+/// same two-step convention: the host writes its serialized result into the host buffer and reports
+/// only the length via a `MaybeUninit<usize>` out-param, and the caller then copies the bytes across
+/// with a single call to this function. `try_get_named_arg`, `get_call_stack` and
+/// `call_contract_with_flags` do not use it; they copy their results with their own host calls.
+pub fn read_host_buffer(size: usize) -> Result<Vec<u8>, Error> {
+    let mut dest: Vec<u8> = if size == 0 {
+        Vec::new()
     } else {
-        None
-    }
+        let dest_ptr = contract_api::alloc_bytes(size);
+        unsafe { Vec::from_raw_parts(dest_ptr, size, size) }
+    };
+    let mut bytes_written = MaybeUninit::uninit();
+    let result_value = unsafe {
+        ext_ffi::read_host_buffer(dest.as_mut_ptr(), dest.len(), bytes_written.as_mut_ptr())
+    };
+    error::result_from(result_value)?;
+    Ok(dest)
 }
 
 /// Return the i-th argument passed to the host for the current module
 /// invocation. Note that this is only relevant to contracts stored on-chain
 /// since a contract deployed directly is not invoked with any arguments.
 pub fn get_arg<T: CLTyped + FromBytes>(i: u32) -> Option<Result<T, CLValueError>> {
-    let arg_size = load_arg(i)?;
-    let arg_bytes = {
-        let dest_ptr = contract_api::alloc_bytes(arg_size);
-        unsafe {
-            ext_ffi::get_arg(dest_ptr);
-            Vec::from_raw_parts(dest_ptr, arg_size, arg_size)
+    let arg_size = {
+        let mut arg_size = MaybeUninit::uninit();
+        let result_value = unsafe { ext_ffi::get_arg(i, arg_size.as_mut_ptr()) };
+        match error::result_from(result_value) {
+            Ok(()) => unsafe { arg_size.assume_init() },
+            Err(Error::MissingArgument) => return None,
+            Err(error) => revert(error),
         }
     };
+    let arg_bytes = read_host_buffer(arg_size).unwrap_or_revert();
     Some(
         deserialize::<CLValue>(&arg_bytes)
             .map_err(CLValueError::Serialization)
@@ -104,31 +238,190 @@ pub fn get_arg<T: CLTyped + FromBytes>(i: u32) -> Option<Result<T, CLValueError>
     )
 }
 
+fn named_arg_size(name: &str) -> Option<usize> {
+    let (name_ptr, name_size, _bytes) = contract_api::str_ref_to_ptr(name);
+    let arg_size = unsafe { ext_ffi::get_named_arg_size(name_ptr, name_size) };
+    if arg_size >= 0 {
+        Some(arg_size as usize)
+    } else {
+        None
+    }
+}
+
+/// Return the argument passed to the current module under the given `name`, looked up against the
+/// `Parameter` list declared in the entry point. Reverts if the argument is present but cannot be
+/// deserialized into `T`; returns `None` when no argument with that name was supplied.
+pub fn try_get_named_arg<T: CLTyped + FromBytes>(name: &str) -> Option<T> {
+    let arg_size = named_arg_size(name)?;
+    let arg_bytes = {
+        let dest_ptr = contract_api::alloc_bytes(arg_size);
+        let (name_ptr, name_size, _bytes) = contract_api::str_ref_to_ptr(name);
+        unsafe {
+            ext_ffi::get_named_arg(name_ptr, name_size, dest_ptr);
+            Vec::from_raw_parts(dest_ptr, arg_size, arg_size)
+        }
+    };
+    let cl_value: CLValue = deserialize(&arg_bytes).unwrap_or_revert();
+    Some(cl_value.to_t().unwrap_or_revert())
+}
+
+/// Return the argument passed to the current module under the given `name`, reverting with
+/// [`Error::MissingArgument`] if it was not supplied. See [`try_get_named_arg`] for the fallible
+/// variant.
+pub fn get_named_arg<T: CLTyped + FromBytes>(name: &str) -> T {
+    try_get_named_arg(name).unwrap_or_revert_with(Error::MissingArgument)
+}
+
 /// Returns caller of current context.
 /// When in root context (not in the sub call) - returns None.
 /// When in the sub call - returns public key of the account that made the
 /// deploy.
 pub fn get_caller() -> PublicKey {
-    //  TODO: Once `PUBLIC_KEY_SIZE` is fixed, replace 36 with it.
-    let dest_ptr = contract_api::alloc_bytes(36);
-    unsafe { ext_ffi::get_caller(dest_ptr) };
-    let bytes = unsafe { Vec::from_raw_parts(dest_ptr, 36, 36) };
+    let output_size = {
+        let mut output_size = MaybeUninit::uninit();
+        let result_value = unsafe { ext_ffi::get_caller(output_size.as_mut_ptr()) };
+        error::result_from(result_value).unwrap_or_revert();
+        unsafe { output_size.assume_init() }
+    };
+    let bytes = read_host_buffer(output_size).unwrap_or_revert();
     deserialize(&bytes).unwrap_or_revert()
 }
 
-pub fn get_blocktime() -> BlockTime {
-    let dest_ptr = contract_api::alloc_bytes(BLOCKTIME_SER_SIZE);
+/// Length in bytes of a 256-bit digest.
+const DIGEST_LENGTH: usize = 32;
+/// Length in bytes of an Ethereum-style address.
+const ETH_ADDRESS_LENGTH: usize = 20;
+/// Length in bytes of a compressed secp256k1 public key.
+const COMPRESSED_PUBKEY_LENGTH: usize = 33;
+
+/// Computes the BLAKE2b-256 digest of `input` via the host.
+pub fn blake2b(input: &[u8]) -> [u8; DIGEST_LENGTH] {
+    let mut out = [0u8; DIGEST_LENGTH];
+    let result_value =
+        unsafe { ext_ffi::blake2b(input.as_ptr(), input.len(), out.as_mut_ptr()) };
+    error::result_from(result_value).unwrap_or_revert();
+    out
+}
+
+/// Computes the Keccak-256 digest of `input` via the host.
+pub fn keccak256(input: &[u8]) -> [u8; DIGEST_LENGTH] {
+    let mut out = [0u8; DIGEST_LENGTH];
+    let result_value =
+        unsafe { ext_ffi::keccak256(input.as_ptr(), input.len(), out.as_mut_ptr()) };
+    error::result_from(result_value).unwrap_or_revert();
+    out
+}
+
+/// Computes the SHA-256 digest of `input` via the host.
+pub fn sha256(input: &[u8]) -> [u8; DIGEST_LENGTH] {
+    let mut out = [0u8; DIGEST_LENGTH];
+    let result_value =
+        unsafe { ext_ffi::sha256(input.as_ptr(), input.len(), out.as_mut_ptr()) };
+    error::result_from(result_value).unwrap_or_revert();
+    out
+}
+
+/// Recovers the Ethereum-style address (the last 20 bytes of the Keccak-256 of the uncompressed
+/// public key) from a compressed secp256k1 public key. Reverts cleanly if the key is malformed.
+pub fn ecdsa_recover_eth_address(
+    compressed_pubkey: &[u8; COMPRESSED_PUBKEY_LENGTH],
+) -> [u8; ETH_ADDRESS_LENGTH] {
+    let mut out = [0u8; ETH_ADDRESS_LENGTH];
+    let result_value = unsafe {
+        ext_ffi::ecdsa_recover_eth_address(
+            compressed_pubkey.as_ptr(),
+            compressed_pubkey.len(),
+            out.as_mut_ptr(),
+        )
+    };
+    error::result_from(result_value).unwrap_or_revert();
+    out
+}
+
+/// Returns the current call stack, ordered from the originating session account down to the frame
+/// currently executing. This is richer than [`get_caller`], which only reports the immediate
+/// deployer, and is the basis for reentrancy guards and full-chain authorization.
+pub fn get_call_stack() -> Vec<CallStackElement> {
+    let bytes_size = unsafe { ext_ffi::load_call_stack() };
+    let dest_ptr = contract_api::alloc_bytes(bytes_size);
     let bytes = unsafe {
-        ext_ffi::get_blocktime(dest_ptr);
-        Vec::from_raw_parts(dest_ptr, BLOCKTIME_SER_SIZE, BLOCKTIME_SER_SIZE)
+        ext_ffi::get_call_stack(dest_ptr);
+        Vec::from_raw_parts(dest_ptr, bytes_size, bytes_size)
     };
     deserialize(&bytes).unwrap_or_revert()
 }
 
+/// Returns how many times the currently running contract appears in the call stack. A value
+/// greater than one means the contract has been re-entered, letting a guard cheaply bail out.
+pub fn reentrance_count() -> u32 {
+    let call_stack = get_call_stack();
+    let current = call_stack
+        .last()
+        .and_then(CallStackElement::contract_hash)
+        .copied();
+    match current {
+        Some(contract_hash) => call_stack
+            .iter()
+            .filter(|element| element.contract_hash() == Some(&contract_hash))
+            .count() as u32,
+        None => 0,
+    }
+}
+
+/// Returns how many times `account` appears as a session frame in the call stack.
+pub fn account_reentrance_count(account: PublicKey) -> u32 {
+    get_call_stack()
+        .iter()
+        .filter(|element| element.account() == Some(&account))
+        .count() as u32
+}
+
+pub fn get_blocktime() -> BlockTime {
+    let output_size = {
+        let mut output_size = MaybeUninit::uninit();
+        let result_value = unsafe { ext_ffi::get_blocktime(output_size.as_mut_ptr()) };
+        error::result_from(result_value).unwrap_or_revert();
+        unsafe { output_size.assume_init() }
+    };
+    let bytes = read_host_buffer(output_size).unwrap_or_revert();
+    deserialize(&bytes).unwrap_or_revert()
+}
+
 pub fn get_phase() -> Phase {
-    let dest_ptr = contract_api::alloc_bytes(PHASE_SIZE);
-    unsafe { ext_ffi::get_phase(dest_ptr) };
-    let bytes = unsafe { Vec::from_raw_parts(dest_ptr, PHASE_SIZE, PHASE_SIZE) };
+    let output_size = {
+        let mut output_size = MaybeUninit::uninit();
+        let result_value = unsafe { ext_ffi::get_phase(output_size.as_mut_ptr()) };
+        error::result_from(result_value).unwrap_or_revert();
+        unsafe { output_size.assume_init() }
+    };
+    let bytes = read_host_buffer(output_size).unwrap_or_revert();
+    deserialize(&bytes).unwrap_or_revert()
+}
+
+/// Returns the gas still available to the current module. A long-running loop can checkpoint
+/// against this and [`ret`] early rather than trapping with an out-of-gas error mid-write.
+pub fn get_gas_left() -> Gas {
+    let output_size = {
+        let mut output_size = MaybeUninit::uninit();
+        let result_value = unsafe { ext_ffi::get_gas_left(output_size.as_mut_ptr()) };
+        error::result_from(result_value).unwrap_or_revert();
+        unsafe { output_size.assume_init() }
+    };
+    let bytes = read_host_buffer(output_size).unwrap_or_revert();
+    let gas_left: U512 = deserialize(&bytes).unwrap_or_revert();
+    Gas::new(gas_left)
+}
+
+/// Returns the network's minimum (existence) balance, so a transfer can be rejected before it would
+/// strand a dust balance below the threshold.
+pub fn get_minimum_balance() -> U512 {
+    let output_size = {
+        let mut output_size = MaybeUninit::uninit();
+        let result_value = unsafe { ext_ffi::get_minimum_balance(output_size.as_mut_ptr()) };
+        error::result_from(result_value).unwrap_or_revert();
+        unsafe { output_size.assume_init() }
+    };
+    let bytes = read_host_buffer(output_size).unwrap_or_revert();
     deserialize(&bytes).unwrap_or_revert()
 }
 
@@ -137,21 +430,46 @@ pub fn get_phase() -> Phase {
 /// depending on whether the current module is a sub-call or not.
 pub fn get_key(name: &str) -> Option<Key> {
     let (name_ptr, name_size, _bytes) = contract_api::str_ref_to_ptr(name);
-    let key_size = unsafe { ext_ffi::get_key(name_ptr, name_size) };
-    let dest_ptr = contract_api::alloc_bytes(key_size);
-    let key_bytes = unsafe {
-        // TODO: unify FFIs that just copy from the host buffer
-        // https://casperlabs.atlassian.net/browse/EE-426
-        ext_ffi::get_arg(dest_ptr);
-        Vec::from_raw_parts(dest_ptr, key_size, key_size)
-    };
-    // TODO: better error handling (i.e. pass the `Result` on)
+    let key_size = {
+        let mut key_size = MaybeUninit::uninit();
+        let result_value = unsafe { ext_ffi::get_key(name_ptr, name_size, key_size.as_mut_ptr()) };
+        error::result_from(result_value).unwrap_or_revert();
+        unsafe { key_size.assume_init() }
+    };
+    let key_bytes = read_host_buffer(key_size).unwrap_or_revert();
+    // The host serializes the lookup result as a `CLValue` wrapping an `Option<Key>`.
     deserialize::<CLValue>(&key_bytes)
         .unwrap_or_revert()
         .to_t()
         .unwrap_or_revert()
 }
 
+/// Emit a structured log record from the currently running stored contract. Each of `topics` is
+/// hashed and indexed by the host so off-chain consumers can filter on them, and `data` carries an
+/// arbitrary payload. This records a new persisted effect for the deploy rather than mutating the
+/// contract's named keys.
+pub fn emit_event(topics: &[&str], data: CLValue) {
+    let topic_strings: Vec<String> = topics.iter().map(|topic| topic.to_string()).collect();
+    let (topics_ptr, topics_size, _bytes) = contract_api::to_ptr(topic_strings);
+    let (data_ptr, data_size, _bytes2) = contract_api::to_ptr(data);
+    unsafe {
+        ext_ffi::deposit_event(topics_ptr, topics_size, data_ptr, data_size);
+    }
+}
+
+/// Returns the number of events emitted by the current contract so far, letting a contract assign
+/// monotonic sequence numbers to its own events.
+pub fn event_count() -> u64 {
+    let output_size = {
+        let mut output_size = MaybeUninit::uninit();
+        let result_value = unsafe { ext_ffi::event_count(output_size.as_mut_ptr()) };
+        error::result_from(result_value).unwrap_or_revert();
+        unsafe { output_size.assume_init() }
+    };
+    let bytes = read_host_buffer(output_size).unwrap_or_revert();
+    deserialize(&bytes).unwrap_or_revert()
+}
+
 /// Check if the given name corresponds to a known unforgable reference
 pub fn has_key(name: &str) -> bool {
     let (name_ptr, name_size, _bytes) = contract_api::str_ref_to_ptr(name);
@@ -173,12 +491,13 @@ pub fn remove_key(name: &str) {
 }
 
 pub fn list_named_keys() -> BTreeMap<String, Key> {
-    let bytes_size = unsafe { ext_ffi::load_named_keys() };
-    let dest_ptr = contract_api::alloc_bytes(bytes_size);
-    let bytes = unsafe {
-        ext_ffi::list_named_keys(dest_ptr);
-        Vec::from_raw_parts(dest_ptr, bytes_size, bytes_size)
+    let bytes_size = {
+        let mut bytes_size = MaybeUninit::uninit();
+        let result_value = unsafe { ext_ffi::load_named_keys(bytes_size.as_mut_ptr()) };
+        error::result_from(result_value).unwrap_or_revert();
+        unsafe { bytes_size.assume_init() }
     };
+    let bytes = read_host_buffer(bytes_size).unwrap_or_revert();
     deserialize::<CLValue>(&bytes)
         .unwrap_or_revert()
         .to_t()
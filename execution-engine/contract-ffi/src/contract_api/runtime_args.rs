@@ -0,0 +1,125 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    value::{CLTyped, CLValue, CLValueError},
+};
+
+/// An ordered collection of named arguments passed to a contract entry point.
+///
+/// Unlike the positional arguments consumed by [`get_arg`](super::runtime::get_arg), a
+/// `RuntimeArgs` pairs every `CLValue` with the `String` name under which the callee's
+/// `EntryPoint` declared its matching `Parameter`. Ordering is preserved so that contracts
+/// relying on the legacy positional shim keep observing arguments in insertion order.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct RuntimeArgs(Vec<(String, CLValue)>);
+
+impl RuntimeArgs {
+    /// Creates an empty set of runtime arguments.
+    pub fn new() -> RuntimeArgs {
+        RuntimeArgs(Vec::new())
+    }
+
+    /// Inserts a named argument, serializing `value` into a `CLValue`.
+    pub fn insert<K: Into<String>, V: CLTyped + ToBytes>(
+        &mut self,
+        key: K,
+        value: V,
+    ) -> Result<(), CLValueError> {
+        let cl_value = CLValue::from_t(value)?;
+        self.0.push((key.into(), cl_value));
+        Ok(())
+    }
+
+    /// Returns the `CLValue` stored under `name`, if any.
+    pub fn get(&self, name: &str) -> Option<&CLValue> {
+        self.0
+            .iter()
+            .find_map(|(key, value)| if key == name { Some(value) } else { None })
+    }
+
+    /// Returns the number of arguments.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if no arguments are present.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<Vec<(String, CLValue)>> for RuntimeArgs {
+    fn from(values: Vec<(String, CLValue)>) -> RuntimeArgs {
+        RuntimeArgs(values)
+    }
+}
+
+impl ToBytes for RuntimeArgs {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        self.0.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.0.serialized_length()
+    }
+}
+
+impl FromBytes for RuntimeArgs {
+    fn from_bytes(bytes: &[u8]) -> Result<(RuntimeArgs, &[u8]), bytesrepr::Error> {
+        let (args, remainder) = Vec::<(String, CLValue)>::from_bytes(bytes)?;
+        Ok((RuntimeArgs(args), remainder))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    use super::*;
+
+    fn sample_args() -> RuntimeArgs {
+        let mut args = RuntimeArgs::new();
+        args.insert("target", 1u32).unwrap();
+        args.insert("amount", 2u64).unwrap();
+        args
+    }
+
+    #[test]
+    fn should_start_empty() {
+        let args = RuntimeArgs::new();
+        assert!(args.is_empty());
+        assert_eq!(args.len(), 0);
+    }
+
+    #[test]
+    fn should_track_len_and_emptiness() {
+        let args = sample_args();
+        assert!(!args.is_empty());
+        assert_eq!(args.len(), 2);
+    }
+
+    #[test]
+    fn should_get_by_name() {
+        let args = sample_args();
+        assert_eq!(args.get("amount"), Some(&CLValue::from_t(2u64).unwrap()));
+        assert_eq!(args.get("missing"), None);
+    }
+
+    #[test]
+    fn should_preserve_insertion_order() {
+        let args = sample_args();
+        let names: Vec<&str> = args.0.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["target", "amount"]);
+    }
+
+    #[test]
+    fn should_round_trip_bytesrepr() {
+        let args = sample_args();
+        let bytes = args.to_bytes().expect("should serialize");
+        assert_eq!(bytes.len(), args.serialized_length());
+        let (parsed, remainder) = RuntimeArgs::from_bytes(&bytes).expect("should deserialize");
+        assert_eq!(parsed, args);
+        assert!(remainder.is_empty());
+    }
+}
@@ -0,0 +1,90 @@
+use core::mem::MaybeUninit;
+
+use crate::{
+    bytesrepr::{deserialize, FromBytes, ToBytes},
+    contract_api::{
+        self,
+        error::{self, Error},
+        runtime,
+    },
+    ext_ffi,
+    unwrap_or_revert::UnwrapOrRevert,
+    uref::URef,
+    value::{CLTyped, CLValue},
+};
+
+/// Maximum length, in bytes, of the string key addressing an item within a dictionary.
+pub const DICTIONARY_ITEM_KEY_MAX_LENGTH: usize = 128;
+
+/// Returns the `(ptr, len)` of a dictionary item key, reverting if it exceeds
+/// [`DICTIONARY_ITEM_KEY_MAX_LENGTH`].
+fn dictionary_item_key_to_ptr(dictionary_item_key: &str) -> (*const u8, usize) {
+    if dictionary_item_key.len() > DICTIONARY_ITEM_KEY_MAX_LENGTH {
+        runtime::revert(Error::DictionaryItemKeyExceedsLength);
+    }
+    (dictionary_item_key.as_ptr(), dictionary_item_key.len())
+}
+
+/// Creates a new dictionary seed [`URef`], registers it under the named key `name`, and returns it.
+/// Items are subsequently addressed by `(seed_addr, blake2b(item_key))`, so the collection never has
+/// to be materialized in full the way a `named_keys` entry would.
+pub fn new_dictionary(name: &str) -> URef {
+    let (name_ptr, name_size, _bytes) = contract_api::str_ref_to_ptr(name);
+    let value_size = {
+        let mut value_size = MaybeUninit::uninit();
+        let result_value =
+            unsafe { ext_ffi::new_dictionary(name_ptr, name_size, value_size.as_mut_ptr()) };
+        error::result_from(result_value).unwrap_or_revert();
+        unsafe { value_size.assume_init() }
+    };
+    let value_bytes = runtime::read_host_buffer(value_size).unwrap_or_revert();
+    deserialize(&value_bytes).unwrap_or_revert()
+}
+
+/// Reads the item stored under `dictionary_item_key` in the dictionary seeded by `seed_uref`,
+/// returning `None` if no such item exists.
+pub fn dictionary_get<T: CLTyped + FromBytes>(
+    seed_uref: URef,
+    dictionary_item_key: &str,
+) -> Option<T> {
+    let (uref_ptr, uref_size, _bytes) = contract_api::to_ptr(seed_uref);
+    let (key_ptr, key_size) = dictionary_item_key_to_ptr(dictionary_item_key);
+    let value_size = {
+        let mut value_size = MaybeUninit::uninit();
+        let result_value = unsafe {
+            ext_ffi::dictionary_get(
+                uref_ptr,
+                uref_size,
+                key_ptr,
+                key_size,
+                value_size.as_mut_ptr(),
+            )
+        };
+        match error::result_from(result_value) {
+            Ok(()) => unsafe { value_size.assume_init() },
+            Err(Error::ValueNotFound) => return None,
+            Err(error) => runtime::revert(error),
+        }
+    };
+    let value_bytes = runtime::read_host_buffer(value_size).unwrap_or_revert();
+    let cl_value: CLValue = deserialize(&value_bytes).unwrap_or_revert();
+    Some(cl_value.to_t().unwrap_or_revert())
+}
+
+/// Writes `value` under `dictionary_item_key` in the dictionary seeded by `seed_uref`.
+pub fn dictionary_put<T: CLTyped + ToBytes>(
+    seed_uref: URef,
+    dictionary_item_key: &str,
+    value: T,
+) {
+    let (uref_ptr, uref_size, _bytes1) = contract_api::to_ptr(seed_uref);
+    let (key_ptr, key_size) = dictionary_item_key_to_ptr(dictionary_item_key);
+    let cl_value = CLValue::from_t(value).unwrap_or_revert();
+    let (value_ptr, value_size, _bytes2) = contract_api::to_ptr(cl_value);
+    let result_value = unsafe {
+        ext_ffi::dictionary_put(
+            uref_ptr, uref_size, key_ptr, key_size, value_ptr, value_size,
+        )
+    };
+    error::result_from(result_value).unwrap_or_revert();
+}